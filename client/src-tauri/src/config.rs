@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// How the client reaches the embedded server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Transport {
+    /// Plain TCP loopback on `port` (the historical, default behavior).
+    #[default]
+    Tcp,
+    /// A per-user local endpoint: a Unix domain socket on macOS/Linux, a
+    /// named pipe on Windows. Not reachable over the network; its path is
+    /// scoped to the current user, but no access permissions are set on it.
+    LocalSocket,
+}
+
+/// Where to find and how to launch the embedded Nyx server.
+///
+/// Loaded from a `nyx.toml` or `nyx.yaml` file placed next to the
+/// application executable; any field left unset falls back to the value
+/// in [`ServerConfig::default`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub command: String,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub env: HashMap<String, String>,
+    pub port: u16,
+    pub health_path: String,
+    pub startup_timeout_secs: u64,
+    pub transport: Transport,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            command: "node".to_string(),
+            args: vec!["./server/start.js".to_string()],
+            cwd: Some(PathBuf::from("./server")),
+            env: HashMap::new(),
+            port: 3000,
+            health_path: "/health".to_string(),
+            startup_timeout_secs: 30,
+            transport: Transport::Tcp,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Looks for `nyx.toml` then `nyx.yaml` in `dir`, falling back to
+    /// [`ServerConfig::default`] if neither is present or fails to parse.
+    pub fn load_from_dir(dir: &Path) -> Self {
+        let candidates: [(&str, fn(&str) -> Result<ServerConfig, String>); 2] = [
+            ("nyx.toml", parse_toml),
+            ("nyx.yaml", parse_yaml),
+        ];
+
+        for (name, parse) in candidates {
+            let path = dir.join(name);
+            if !path.exists() {
+                continue;
+            }
+
+            match std::fs::read_to_string(&path) {
+                Ok(contents) => match parse(&contents) {
+                    Ok(config) => {
+                        log::info!("Loaded server config from {}", path.display());
+                        return config;
+                    }
+                    Err(e) => log::warn!("Failed to parse {}: {}", path.display(), e),
+                },
+                Err(e) => log::warn!("Failed to read {}: {}", path.display(), e),
+            }
+        }
+
+        log::info!(
+            "No nyx.toml/nyx.yaml found in {}, using default server config",
+            dir.display()
+        );
+        Self::default()
+    }
+
+    /// Resolves `command` to an absolute path via `PATH` when it isn't one
+    /// already, so configured commands like `node`/`pnpm` are found
+    /// reliably across platforms.
+    pub fn resolve_command(&self) -> Result<PathBuf, String> {
+        let path = PathBuf::from(&self.command);
+        if path.is_absolute() {
+            return Ok(path);
+        }
+
+        which::which(&self.command)
+            .map_err(|e| format!("Could not find '{}' on PATH: {}", self.command, e))
+    }
+
+    pub fn health_url(&self) -> String {
+        format!("http://localhost:{}{}", self.port, self.health_path)
+    }
+
+    pub fn capabilities_url(&self) -> String {
+        format!("http://localhost:{}/capabilities", self.port)
+    }
+}
+
+fn parse_toml(contents: &str) -> Result<ServerConfig, String> {
+    toml::from_str(contents).map_err(|e| e.to_string())
+}
+
+fn parse_yaml(contents: &str) -> Result<ServerConfig, String> {
+    serde_yaml::from_str(contents).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh scratch directory for one test, namespaced by test name and
+    /// pid so parallel test runs don't collide.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nyx-config-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("failed to create scratch dir");
+        dir
+    }
+
+    #[test]
+    fn load_from_dir_prefers_toml_over_yaml() {
+        let dir = scratch_dir("toml-over-yaml");
+        std::fs::write(dir.join("nyx.toml"), "command = \"from-toml\"\n").unwrap();
+        std::fs::write(dir.join("nyx.yaml"), "command: from-yaml\n").unwrap();
+
+        let config = ServerConfig::load_from_dir(&dir);
+
+        assert_eq!(config.command, "from-toml");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_from_dir_falls_back_to_yaml_when_no_toml() {
+        let dir = scratch_dir("yaml-fallback");
+        std::fs::write(dir.join("nyx.yaml"), "command: from-yaml\n").unwrap();
+
+        let config = ServerConfig::load_from_dir(&dir);
+
+        assert_eq!(config.command, "from-yaml");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_from_dir_defaults_when_neither_file_present() {
+        let dir = scratch_dir("no-config");
+
+        let config = ServerConfig::load_from_dir(&dir);
+
+        assert_eq!(config.command, ServerConfig::default().command);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
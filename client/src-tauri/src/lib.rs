@@ -1,12 +1,164 @@
-use std::process::{Command, Stdio};
+mod config;
+mod transport;
+
 use std::path::PathBuf;
-use tauri::Manager;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use rand::Rng;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
 use log;
 
+use config::ServerConfig;
+
+/// Capabilities every supported server version must advertise before we
+/// declare it ready.
+const REQUIRED_SERVER_CAPABILITIES: &[&str] = &["automation", "profiles", "proxy"];
+
+/// Semver range of server versions this client knows how to talk to.
+const REQUIRED_SERVER_VERSION: &str = ">=1.0.0";
+
+/// The capability set a running server advertises, read from `/capabilities`
+/// or the `/health` response body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerCapabilities {
+    pub version: String,
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl ServerCapabilities {
+    fn missing_capabilities(&self) -> Vec<&str> {
+        REQUIRED_SERVER_CAPABILITIES
+            .iter()
+            .filter(|cap| !self.features.iter().any(|f| f == *cap))
+            .copied()
+            .collect()
+    }
+}
+
+/// Tracks the embedded server's child process so it can be stopped or
+/// restarted instead of being spawned and forgotten.
+///
+/// Uses a `tokio::sync::Mutex` (rather than `std::sync::Mutex`) so
+/// `store_child` can hold the lock across the `.await` in `graceful_stop`,
+/// serializing the store step of concurrent start attempts (e.g. the
+/// setup-time auto-start racing a user-triggered start/restart) instead of
+/// letting them overwrite one another's tracked child. The spawns themselves
+/// still run concurrently; the later store stops the earlier child.
+#[derive(Default)]
+struct ServerProcess {
+    child: Mutex<Option<Child>>,
+}
+
+/// How long to wait for the server to exit gracefully before killing it.
+const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tracks `child` as the managed server process, stopping any previously
+/// tracked process first so starting the server twice doesn't leak an
+/// untracked, still-running orphan. Holds `state.child`'s lock for the
+/// entire replace-then-store sequence so two concurrent calls can't both
+/// observe no previous child and silently clobber each other's `Child`.
+async fn store_child(state: &tauri::State<'_, ServerProcess>, child: Child) {
+    let mut guard = state.child.lock().await;
+
+    if let Some(mut previous) = guard.take() {
+        let already_exited = matches!(previous.try_wait(), Ok(Some(_)));
+        if !already_exited {
+            log::warn!("Replacing a tracked server process that is still running; stopping it first");
+            if let Err(e) = graceful_stop(previous).await {
+                log::warn!("Failed to stop previous server process: {}", e);
+            }
+        }
+    }
+
+    *guard = Some(child);
+}
+
+/// Sends a graceful shutdown request to `child` without waiting for it to exit.
+async fn send_graceful_shutdown(child: &Child) -> Result<(), String> {
+    let Some(pid) = child.id() else {
+        // Already reaped, nothing to signal.
+        return Ok(());
+    };
+
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("taskkill")
+            .args(["/PID", &pid.to_string()])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to send shutdown signal: {}", e))?;
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Command::new("kill")
+            .args(["-TERM", &pid.to_string()])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to send shutdown signal: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// A single line of captured server stdout/stderr, forwarded to the frontend.
+#[derive(Clone, Serialize)]
+struct ServerLogEvent {
+    level: &'static str,
+    line: String,
+}
+
+/// Reads `reader` line-by-line until EOF, emitting each line as a
+/// `server-log` event tagged with `level` ("stdout"/"stderr").
+fn spawn_log_forwarder<R>(app_handle: tauri::AppHandle, reader: R, level: &'static str)
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => {
+                    if level == "stderr" {
+                        log::warn!("[server] {}", line);
+                    } else {
+                        log::info!("[server] {}", line);
+                    }
+                    if let Err(e) = app_handle.emit("server-log", ServerLogEvent { level, line }) {
+                        log::warn!("Failed to emit server-log event: {}", e);
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    log::warn!("Error reading server {}: {}", level, e);
+                    break;
+                }
+            }
+        }
+    });
+}
+
 #[tauri::command]
-async fn check_server_health() -> Result<bool, String> {
+async fn check_server_health(
+    app_handle: tauri::AppHandle,
+    config: tauri::State<'_, ServerConfig>,
+) -> Result<bool, String> {
+    if config.transport == config::Transport::LocalSocket {
+        let endpoint = transport::endpoint_path(&app_handle)?;
+        return Ok(transport::get(&endpoint, &config.health_path)
+            .await
+            .map(|resp| (200..300).contains(&resp.status))
+            .unwrap_or(false));
+    }
+
     let client = reqwest::Client::new();
-    match client.get("http://localhost:3000/health")
+    match client.get(config.health_url())
         .timeout(std::time::Duration::from_secs(5))
         .send()
         .await
@@ -17,49 +169,42 @@ async fn check_server_health() -> Result<bool, String> {
 }
 
 #[tauri::command]
-async fn start_server() -> Result<String, String> {
-    log::info!("Starting Nyx server...");
-    
-    // Try to find the server executable or script
-    let server_paths = vec![
-        "./server/start.js",
-        "../server/start.js",
-        "./server/dist/nyx-server.exe",
-        "../server/dist/nyx-server.exe",
-        "nyx-server.exe"
-    ];
-
-    for path in server_paths {
-        let path_buf = PathBuf::from(path);
-        if path_buf.exists() {
-            log::info!("Found server at: {}", path);
-            
-            let result = if path.ends_with(".js") {
-                // Run Node.js script
-                log::info!("Starting server as Node.js process...");
-                Command::new("node")
-                    .arg(path)
-                    .current_dir("./server")
-                    .stdout(Stdio::null())
-                    .stderr(Stdio::null())
-                    .spawn()
-            } else {
-                // Run executable
-                log::info!("Starting server as executable...");
-                Command::new(path)
-                    .stdout(Stdio::null())
-                    .stderr(Stdio::null())
-                    .spawn()
-            };
-
-            match result {
-                Ok(_) => return Ok(format!("Server started from {}", path)),
-                Err(e) => log::warn!("Failed to start server from {}: {}", path, e),
+async fn start_server(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, ServerProcess>,
+    config: tauri::State<'_, ServerConfig>,
+) -> Result<String, String> {
+    log::info!("Starting Nyx server using configured command '{}'...", config.command);
+
+    let command_path = config.resolve_command()?;
+
+    let mut cmd = Command::new(&command_path);
+    cmd.args(&config.args);
+    if let Some(cwd) = &config.cwd {
+        cmd.current_dir(cwd);
+    }
+    for (key, value) in &config.env {
+        cmd.env(key, value);
+    }
+    if config.transport == config::Transport::LocalSocket {
+        let endpoint = transport::endpoint_path(&app_handle)?;
+        cmd.env("NYX_SOCKET_PATH", &endpoint);
+    }
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    match cmd.spawn() {
+        Ok(mut child) => {
+            if let Some(stdout) = child.stdout.take() {
+                spawn_log_forwarder(app_handle.clone(), stdout, "stdout");
+            }
+            if let Some(stderr) = child.stderr.take() {
+                spawn_log_forwarder(app_handle.clone(), stderr, "stderr");
             }
+            store_child(&state, child).await;
+            Ok(format!("Server started via {}", command_path.display()))
         }
+        Err(e) => Err(format!("Failed to start server via {}: {}", command_path.display(), e)),
     }
-
-    Err("Could not find or start server executable".to_string())
 }
 
 #[tauri::command]
@@ -70,32 +215,39 @@ async fn open_server_folder() -> Result<(), String> {
         Command::new("explorer")
             .arg("./server")
             .output()
+            .await
             .map_err(|e| format!("Failed to open folder: {}", e))?;
     }
-    
+
     #[cfg(target_os = "macos")]
     {
         Command::new("open")
             .arg("./server")
             .output()
+            .await
             .map_err(|e| format!("Failed to open folder: {}", e))?;
     }
-    
+
     #[cfg(target_os = "linux")]
     {
         Command::new("xdg-open")
             .arg("./server")
             .output()
+            .await
             .map_err(|e| format!("Failed to open folder: {}", e))?;
     }
-    
+
     Ok(())
 }
 
 #[tauri::command]
-async fn start_embedded_server(app_handle: tauri::AppHandle) -> Result<(), String> {
+async fn start_embedded_server(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, ServerProcess>,
+    config: tauri::State<'_, ServerConfig>,
+) -> Result<(), String> {
     log::info!("Starting embedded server...");
-    
+
     // Get the resource path for the server executable
     let resource_path = app_handle
         .path()
@@ -105,21 +257,32 @@ async fn start_embedded_server(app_handle: tauri::AppHandle) -> Result<(), Strin
 
     if !resource_path.exists() {
         log::warn!("Server executable not found in resources, trying alternative methods...");
-        return start_server().await;
+        return start_server(app_handle, state, config).await.map(|_| ());
     }
 
     // Start the server process with better error handling
-    match Command::new(&resource_path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
+    let mut cmd = Command::new(&resource_path);
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    if config.transport == config::Transport::LocalSocket {
+        let endpoint = transport::endpoint_path(&app_handle)?;
+        cmd.env("NYX_SOCKET_PATH", &endpoint);
+    }
+
+    match cmd.spawn()
     {
         Ok(mut child) => {
             log::info!("Server process started, waiting for startup...");
-            
+
+            if let Some(stdout) = child.stdout.take() {
+                spawn_log_forwarder(app_handle.clone(), stdout, "stdout");
+            }
+            if let Some(stderr) = child.stderr.take() {
+                spawn_log_forwarder(app_handle.clone(), stderr, "stderr");
+            }
+
             // Give the server time to start
             tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-            
+
             // Check if the process is still running
             match child.try_wait() {
                 Ok(Some(status)) => {
@@ -128,6 +291,7 @@ async fn start_embedded_server(app_handle: tauri::AppHandle) -> Result<(), Strin
                 }
                 Ok(None) => {
                     log::info!("Server started successfully and is running");
+                    store_child(&state, child).await;
                     Ok(())
                 }
                 Err(e) => {
@@ -143,29 +307,208 @@ async fn start_embedded_server(app_handle: tauri::AppHandle) -> Result<(), Strin
     }
 }
 
+/// Sends a graceful shutdown to `child` and waits for it to exit, falling
+/// back to `kill()` if it hasn't exited within [`GRACEFUL_SHUTDOWN_TIMEOUT`].
+/// Shared by `stop_server` and the app's `RunEvent::Exit` handler so both
+/// give the server the same grace period before killing it.
+async fn graceful_stop(mut child: Child) -> Result<(), String> {
+    log::info!("Sending graceful shutdown to server process (pid {:?})", child.id());
+    send_graceful_shutdown(&child).await?;
+
+    let deadline = Instant::now() + GRACEFUL_SHUTDOWN_TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                log::info!("Server process exited with status: {}", status);
+                return Ok(());
+            }
+            Ok(None) if Instant::now() < deadline => {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            Ok(None) => {
+                log::warn!(
+                    "Server did not exit within {:?}, killing it",
+                    GRACEFUL_SHUTDOWN_TIMEOUT
+                );
+                child.kill().await.map_err(|e| format!("Failed to kill server process: {}", e))?;
+                return Ok(());
+            }
+            Err(e) => return Err(format!("Error waiting for server process to exit: {}", e)),
+        }
+    }
+}
+
+/// Stops the tracked server process, if any. Sends a graceful shutdown first
+/// and falls back to `kill()` if the process hasn't exited within
+/// [`GRACEFUL_SHUTDOWN_TIMEOUT`].
+#[tauri::command]
+async fn stop_server(state: tauri::State<'_, ServerProcess>) -> Result<(), String> {
+    let child_opt = state.child.lock().await.take();
+    match child_opt {
+        Some(child) => graceful_stop(child).await,
+        None => {
+            log::info!("stop_server called but no server process is tracked");
+            Ok(())
+        }
+    }
+}
+
+/// Stops the tracked server process (if running) and starts it again.
+#[tauri::command]
+async fn restart_server(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, ServerProcess>,
+    config: tauri::State<'_, ServerConfig>,
+) -> Result<(), String> {
+    log::info!("Restarting server...");
+    stop_server(state.clone()).await?;
+    start_embedded_server(app_handle, state, config).await
+}
+
+/// Fetches the server's advertised capabilities, preferring a dedicated
+/// `/capabilities` endpoint and falling back to the `/health` response body.
+async fn fetch_server_capabilities(
+    app_handle: &tauri::AppHandle,
+    config: &ServerConfig,
+) -> Result<ServerCapabilities, String> {
+    if config.transport == config::Transport::LocalSocket {
+        let endpoint = transport::endpoint_path(app_handle)?;
+        let response = match transport::get(&endpoint, "/capabilities").await {
+            Ok(resp) if (200..300).contains(&resp.status) => resp,
+            _ => transport::get(&endpoint, &config.health_path).await?,
+        };
+        return serde_json::from_slice(&response.body)
+            .map_err(|e| format!("Failed to parse server capabilities: {}", e));
+    }
+
+    let client = reqwest::Client::new();
+
+    let response = match client.get(config.capabilities_url())
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => resp,
+        _ => client.get(config.health_url())
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach server: {}", e))?,
+    };
+
+    response
+        .json::<ServerCapabilities>()
+        .await
+        .map_err(|e| format!("Failed to parse server capabilities: {}", e))
+}
+
+/// Validates that `capabilities` satisfies [`REQUIRED_SERVER_VERSION`] and
+/// advertises every capability in [`REQUIRED_SERVER_CAPABILITIES`].
+fn check_capabilities(capabilities: &ServerCapabilities) -> Result<(), String> {
+    let required_range = VersionReq::parse(REQUIRED_SERVER_VERSION)
+        .expect("REQUIRED_SERVER_VERSION must be a valid semver range");
+    let version = Version::parse(&capabilities.version).map_err(|e| {
+        format!("Server reported an invalid version '{}': {}", capabilities.version, e)
+    })?;
+
+    if !required_range.matches(&version) {
+        return Err(format!(
+            "Server version {} does not satisfy required range {}",
+            capabilities.version, REQUIRED_SERVER_VERSION
+        ));
+    }
+
+    let missing = capabilities.missing_capabilities();
+    if !missing.is_empty() {
+        return Err(format!(
+            "Server is missing required capabilities: {}",
+            missing.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Starting delay and cap for the exponential backoff used while polling
+/// for server readiness.
+const STARTUP_BACKOFF_START: Duration = Duration::from_millis(100);
+const STARTUP_BACKOFF_CAP: Duration = Duration::from_secs(2);
+
+/// Result of waiting for the server to become ready, returned to the
+/// frontend so it can show how the startup actually went.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerReadyResult {
+    pub capabilities: ServerCapabilities,
+    pub attempts: u32,
+    pub elapsed_ms: u64,
+}
+
+/// Single startup orchestrator: try to reach an already-running server,
+/// spawn one if that fails, then poll health with exponential backoff
+/// (bounded by `config.startup_timeout_secs`) until it responds and
+/// negotiates capabilities, or the tracked process dies, or time runs out.
 #[tauri::command]
-async fn wait_for_server_ready() -> Result<bool, String> {
+async fn wait_for_server_ready(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, ServerProcess>,
+    config: tauri::State<'_, ServerConfig>,
+) -> Result<ServerReadyResult, String> {
     log::info!("Waiting for server to be ready...");
-    
-    // Try to connect to server for up to 30 seconds
-    for i in 0..30 {
-        match check_server_health().await {
+    let start = Instant::now();
+    let timeout = Duration::from_secs(config.startup_timeout_secs);
+
+    let mut spawned = false;
+    let mut attempts: u32 = 0;
+    let mut backoff = STARTUP_BACKOFF_START;
+
+    loop {
+        attempts += 1;
+
+        match check_server_health(app_handle.clone(), config.clone()).await {
             Ok(true) => {
-                log::info!("Server is ready!");
-                return Ok(true);
+                let capabilities = fetch_server_capabilities(&app_handle, &config).await?;
+                check_capabilities(&capabilities)?;
+                let elapsed_ms = start.elapsed().as_millis() as u64;
+                log::info!(
+                    "Server ready after {} attempt(s) in {}ms: version {}, capabilities [{}]",
+                    attempts, elapsed_ms, capabilities.version, capabilities.features.join(", ")
+                );
+                return Ok(ServerReadyResult { capabilities, attempts, elapsed_ms });
             }
-            Ok(false) => {
-                log::debug!("Server not ready yet, attempt {}/30", i + 1);
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-            }
-            Err(e) => {
-                log::debug!("Server health check error: {}", e);
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            Ok(false) => log::debug!("Server not ready yet (attempt {})", attempts),
+            Err(e) => log::debug!("Server health check error on attempt {}: {}", attempts, e),
+        }
+
+        if !spawned {
+            spawned = true;
+            log::info!("Server not reachable, spawning it...");
+            if let Err(e) = start_embedded_server(app_handle.clone(), state.clone(), config.clone()).await {
+                log::warn!("Failed to start embedded server: {}, falling back to external start", e);
+                start_server(app_handle.clone(), state.clone(), config.clone()).await?;
             }
         }
+
+        // If the process we're tracking already died, stop polling immediately
+        // instead of burning the rest of the timeout.
+        let exited = {
+            let mut guard = state.child.lock().await;
+            guard.as_mut().and_then(|child| child.try_wait().ok().flatten())
+        };
+        if let Some(status) = exited {
+            return Err(format!("Server process exited early with status: {}", status));
+        }
+
+        if start.elapsed() >= timeout {
+            return Err(format!(
+                "Server failed to become ready within {:?} ({} attempts)",
+                timeout, attempts
+            ));
+        }
+
+        let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..50));
+        tokio::time::sleep(backoff + jitter).await;
+        backoff = (backoff * 2).min(STARTUP_BACKOFF_CAP);
     }
-    
-    Err("Server failed to start within 30 seconds".to_string())
 }
 
 #[tauri::command]
@@ -177,44 +520,95 @@ fn greet(name: &str) -> String {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_log::Builder::new().build())
+        .manage(ServerProcess::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             check_server_health,
             start_server,
             open_server_folder,
             start_embedded_server,
+            stop_server,
+            restart_server,
             wait_for_server_ready
         ])
         .setup(|app| {
-            // Auto-check server health on startup
+            // Config lives next to the executable so packaged and dev builds
+            // can both override it without rebuilding.
+            let config_dir = std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(|p| p.to_path_buf()))
+                .unwrap_or_else(|| PathBuf::from("."));
+            app.manage(ServerConfig::load_from_dir(&config_dir));
+
+            // Auto-start (or adopt) the server on startup.
             let app_handle = app.handle().clone();
             tokio::spawn(async move {
                 // Wait a moment for the app to fully initialize
                 tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                
-                // Check if server is running
-                match check_server_health().await {
-                    Ok(true) => log::info!("Server is already running"),
-                    Ok(false) => {
-                        log::info!("Server not running, starting embedded server...");
-                        match start_embedded_server(app_handle.clone()).await {
-                            Ok(_) => log::info!("Embedded server started successfully"),
-                            Err(e) => {
-                                log::warn!("Failed to start embedded server: {}", e);
-                                // Fallback to external server start
-                                match start_server().await {
-                                    Ok(msg) => log::info!("Fallback server start: {}", msg),
-                                    Err(e2) => log::error!("All server start methods failed: {}", e2),
-                                }
-                            }
-                        }
-                    },
-                    Err(e) => log::error!("Error checking server health: {}", e),
+
+                let state = app_handle.state::<ServerProcess>();
+                let config = app_handle.state::<ServerConfig>();
+                match wait_for_server_ready(app_handle.clone(), state, config).await {
+                    Ok(result) => log::info!(
+                        "Server ready after {} attempt(s) in {}ms (version {})",
+                        result.attempts, result.elapsed_ms, result.capabilities.version
+                    ),
+                    Err(e) => log::error!("Server failed to become ready: {}", e),
                 }
             });
-            
+
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
\ No newline at end of file
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            // Make sure we don't leave an orphaned server process behind on exit.
+            if let tauri::RunEvent::Exit = event {
+                let state = app_handle.state::<ServerProcess>();
+                tauri::async_runtime::block_on(async {
+                    if let Some(child) = state.child.lock().await.take() {
+                        log::info!("App exiting, stopping server process...");
+                        if let Err(e) = graceful_stop(child).await {
+                            log::warn!("Error stopping server process on exit: {}", e);
+                        }
+                    }
+                });
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(version: &str, features: &[&str]) -> ServerCapabilities {
+        ServerCapabilities {
+            version: version.to_string(),
+            features: features.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn missing_capabilities_reports_absent_required_features() {
+        let caps = capabilities("1.2.0", &["automation", "proxy"]);
+        assert_eq!(caps.missing_capabilities(), vec!["profiles"]);
+    }
+
+    #[test]
+    fn missing_capabilities_empty_when_all_required_present() {
+        let caps = capabilities("1.2.0", &["automation", "profiles", "proxy"]);
+        assert!(caps.missing_capabilities().is_empty());
+    }
+
+    #[test]
+    fn check_capabilities_rejects_out_of_range_version() {
+        let caps = capabilities("0.9.0", &["automation", "profiles", "proxy"]);
+        assert!(check_capabilities(&caps).is_err());
+    }
+
+    #[test]
+    fn check_capabilities_accepts_in_range_version_with_all_features() {
+        let caps = capabilities("1.2.0", &["automation", "profiles", "proxy"]);
+        assert!(check_capabilities(&caps).is_ok());
+    }
+}
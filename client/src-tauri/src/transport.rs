@@ -0,0 +1,145 @@
+//! Local (non-TCP) transport for reaching the embedded server: a Unix
+//! domain socket under the app data dir on macOS/Linux, a named pipe on
+//! Windows. Used instead of TCP loopback when `ServerConfig::transport` is
+//! [`crate::config::Transport::LocalSocket`], so the embedded server isn't
+//! exposed on the network. The endpoint path is scoped to the current user;
+//! this module sets no permissions on the socket, its directory or the pipe.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tauri::Manager;
+
+/// The result of a request made over the local transport.
+pub struct LocalResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+/// Matches the `.timeout(Duration::from_secs(5))` used on the TCP/reqwest
+/// path, so a hung local socket/pipe can't block the caller indefinitely.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Identifies the current process owner, used to scope the socket/pipe
+/// path so it doesn't collide with another user's instance of the app.
+fn current_owner() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Derives the per-user local endpoint path for this app, rooted under the
+/// app data dir on Unix and under the named pipe namespace on Windows.
+pub fn endpoint_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let identifier = &app_handle.config().identifier;
+    let owner = current_owner();
+
+    #[cfg(unix)]
+    {
+        let app_data_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+        std::fs::create_dir_all(&app_data_dir)
+            .map_err(|e| format!("Failed to create app data dir: {}", e))?;
+        Ok(app_data_dir.join(format!("{identifier}-{owner}.sock")))
+    }
+
+    #[cfg(windows)]
+    {
+        Ok(PathBuf::from(format!(r"\\.\pipe\{identifier}-{owner}")))
+    }
+}
+
+/// Issues a bare HTTP/1.1 GET for `request_path` over the local endpoint at
+/// `endpoint`, bounded by [`REQUEST_TIMEOUT`] so a peer that never responds
+/// can't hang the caller indefinitely.
+pub async fn get(endpoint: &std::path::Path, request_path: &str) -> Result<LocalResponse, String> {
+    tokio::time::timeout(REQUEST_TIMEOUT, get_uncapped(endpoint, request_path))
+        .await
+        .map_err(|_| format!("Timed out after {:?} waiting for {}", REQUEST_TIMEOUT, request_path))?
+}
+
+/// Performs the actual request: connect to `endpoint`, write a minimal
+/// HTTP/1.1 GET, and read the raw response bytes back. Hand-rolled instead
+/// of pulling in a full HTTP client stack since the local transport only
+/// ever needs a bare GET with no cookies, redirects, or chunked encoding.
+async fn get_uncapped(endpoint: &std::path::Path, request_path: &str) -> Result<LocalResponse, String> {
+    #[cfg(unix)]
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::UnixStream;
+
+        let mut stream = UnixStream::connect(endpoint)
+            .await
+            .map_err(|e| format!("Failed to connect to {}: {}", endpoint.display(), e))?;
+
+        let request = format!(
+            "GET {request_path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"
+        );
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to send request over local socket: {}", e))?;
+
+        let mut raw = Vec::new();
+        stream
+            .read_to_end(&mut raw)
+            .await
+            .map_err(|e| format!("Failed to read response from local socket: {}", e))?;
+
+        parse_http_response(&raw)
+    }
+
+    #[cfg(windows)]
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::windows::named_pipe::ClientOptions;
+
+        let pipe_name = endpoint.to_string_lossy().to_string();
+        let client = ClientOptions::new()
+            .open(&pipe_name)
+            .map_err(|e| format!("Failed to connect to pipe {}: {}", pipe_name, e))?;
+
+        let mut client = client;
+        let request = format!(
+            "GET {request_path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"
+        );
+        client
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to send request over named pipe: {}", e))?;
+
+        let mut raw = Vec::new();
+        client
+            .read_to_end(&mut raw)
+            .await
+            .map_err(|e| format!("Failed to read response from named pipe: {}", e))?;
+
+        parse_http_response(&raw)
+    }
+}
+
+/// Splits a raw HTTP/1.x response into a status code and body, skipping
+/// header parsing since the only thing callers need is the status and the
+/// JSON body.
+fn parse_http_response(raw: &[u8]) -> Result<LocalResponse, String> {
+    let text_prefix_len = raw.len().min(4096);
+    let head = String::from_utf8_lossy(&raw[..text_prefix_len]);
+    let header_end = head
+        .find("\r\n\r\n")
+        .ok_or_else(|| "Malformed HTTP response: no header terminator".to_string())?;
+
+    let status_line = head
+        .lines()
+        .next()
+        .ok_or_else(|| "Malformed HTTP response: empty status line".to_string())?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| format!("Malformed HTTP status line: {}", status_line))?;
+
+    let body = raw[header_end + 4..].to_vec();
+    Ok(LocalResponse { status, body })
+}